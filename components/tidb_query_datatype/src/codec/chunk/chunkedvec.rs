@@ -1,44 +1,339 @@
 use super::*;
 use std::convert::TryFrom;
+use std::marker::PhantomData;
 
 use super::{Error, Result};
-use crate::codec::data_type::Int;
+use crate::codec::data_type::{DateTime, Decimal, Duration, Int, Real};
 use crate::prelude::*;
 use codec::number::{NumberDecoder, NumberEncoder};
 
-pub struct ChunkedVecInt {
+/// A fixed-width scalar type that can be packed into a [`ChunkedVecSized`] column.
+///
+/// This covers every `EvalType` with a constant-size in-memory representation
+/// (`Int`, `Real`, `Decimal`, `DateTime`, `Duration`). Variable-width types
+/// (`Bytes`, `Json`) are stored by [`ChunkedVecVar`] instead.
+pub trait FixedWidth: Copy {
+    /// Number of bytes each element occupies in the packed data buffer.
+    const ELEMENT_SIZE: usize;
+
+    /// Append the little-endian-ish encoding of `self` to the tail of `buf`.
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()>;
+
+    /// Decode a value out of an `ELEMENT_SIZE`-byte slice.
+    ///
+    /// `data` is guaranteed to be exactly `ELEMENT_SIZE` bytes long, but is not
+    /// guaranteed to satisfy `Self`'s alignment requirements, since it is a
+    /// window into a plain `Vec<u8>`. Implementations must decode by value
+    /// (e.g. `read_i64_le`/`from_ne_bytes`) rather than reinterpreting the
+    /// slice as `&Self`.
+    fn decode_from(data: &[u8]) -> Self;
+}
+
+/// A [`FixedWidth`] type whose packed byte representation is *also* a valid
+/// Arrow fixed-width primitive array element, so it can be exported via
+/// [`ChunkedVecSized::into_arrow_buffers`] without further translation.
+///
+/// `Decimal`/`DateTime`/`Duration` intentionally do NOT implement this: their
+/// `FixedWidth` encoding is a raw copy of the in-process Rust struct
+/// (including padding), which is not the same thing as Arrow's decimal128 or
+/// date/time layouts. Exporting those would hand a consumer bytes it cannot
+/// actually interpret as Arrow data.
+pub trait ArrowFixedWidth: FixedWidth {}
+
+impl ArrowFixedWidth for Int {}
+impl ArrowFixedWidth for Real {}
+
+impl FixedWidth for Int {
+    const ELEMENT_SIZE: usize = std::mem::size_of::<Int>();
+
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        buf.write_i64_le(*self)?;
+        Ok(())
+    }
+
+    fn decode_from(data: &[u8]) -> Self {
+        (&data[..]).read_i64_le().unwrap()
+    }
+}
+
+impl FixedWidth for Real {
+    const ELEMENT_SIZE: usize = std::mem::size_of::<Real>();
+
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        buf.write_f64_le(self.into_inner())?;
+        Ok(())
+    }
+
+    fn decode_from(data: &[u8]) -> Self {
+        let v = (&data[..]).read_f64_le().unwrap();
+        Real::new(v).unwrap_or_default()
+    }
+}
+
+impl FixedWidth for Decimal {
+    const ELEMENT_SIZE: usize = std::mem::size_of::<Decimal>();
+
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        // `Decimal` is a plain-old-data struct with no heap pointers, so it is
+        // safe to copy it byte-for-byte; we copy through `u8`, which has no
+        // alignment requirement, to stay clear of unaligned-access UB.
+        let len = buf.len();
+        buf.resize(len + Self::ELEMENT_SIZE, 0);
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self as *const Decimal as *const u8,
+                buf[len..].as_mut_ptr(),
+                Self::ELEMENT_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn decode_from(data: &[u8]) -> Self {
+        let mut value = std::mem::MaybeUninit::<Decimal>::uninit();
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                value.as_mut_ptr() as *mut u8,
+                Self::ELEMENT_SIZE,
+            );
+            value.assume_init()
+        }
+    }
+}
+
+impl FixedWidth for DateTime {
+    const ELEMENT_SIZE: usize = std::mem::size_of::<DateTime>();
+
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        let len = buf.len();
+        buf.resize(len + Self::ELEMENT_SIZE, 0);
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self as *const DateTime as *const u8,
+                buf[len..].as_mut_ptr(),
+                Self::ELEMENT_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn decode_from(data: &[u8]) -> Self {
+        let mut value = std::mem::MaybeUninit::<DateTime>::uninit();
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                value.as_mut_ptr() as *mut u8,
+                Self::ELEMENT_SIZE,
+            );
+            value.assume_init()
+        }
+    }
+}
+
+impl FixedWidth for Duration {
+    const ELEMENT_SIZE: usize = std::mem::size_of::<Duration>();
+
+    fn encode_into(&self, buf: &mut Vec<u8>) -> Result<()> {
+        let len = buf.len();
+        buf.resize(len + Self::ELEMENT_SIZE, 0);
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self as *const Duration as *const u8,
+                buf[len..].as_mut_ptr(),
+                Self::ELEMENT_SIZE,
+            );
+        }
+        Ok(())
+    }
+
+    fn decode_from(data: &[u8]) -> Self {
+        let mut value = std::mem::MaybeUninit::<Duration>::uninit();
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                value.as_mut_ptr() as *mut u8,
+                Self::ELEMENT_SIZE,
+            );
+            value.assume_init()
+        }
+    }
+}
+
+/// Arrow requires the validity bitmap to be padded to a 64-byte boundary;
+/// our in-process bitmap otherwise uses the exact same "1 = valid" packing
+/// (see `append_null_bitmap`), so exporting is just a resize.
+const ARROW_BITMAP_ALIGNMENT: usize = 64;
+
+fn pad_validity_bitmap(bitmap: &[u8]) -> Vec<u8> {
+    let mut padded = bitmap.to_vec();
+    let padded_len =
+        (padded.len() + ARROW_BITMAP_ALIGNMENT - 1) / ARROW_BITMAP_ALIGNMENT * ARROW_BITMAP_ALIGNMENT;
+    padded.resize(padded_len, 0);
+    padded
+}
+
+/// Shared surface of the chunked-column family, so aggregate/scalar code can
+/// be written generically over both the fixed-width and variable-width
+/// layouts.
+pub trait ChunkedVec<T> {
+    /// Create an empty column, pre-sized for `init_cap` rows.
+    fn with_capacity(init_cap: usize) -> Self;
+
+    /// Append a value (or `None` for null) to the column.
+    fn append(&mut self, value: Option<T>) -> Result<()>;
+
+    /// Append null to the chunked vector.
+    fn append_null(&mut self);
+
+    /// Return whether the datum for the row is null or not.
+    fn is_null(&self, row_idx: usize) -> bool;
+
+    /// Return the total rows in the column.
+    fn len(&self) -> usize;
+
+    /// Return whether the column is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the datum of the row in the chunked vector, or `None` if it is null.
+    fn get_ref(&self, row_idx: usize) -> Option<T>;
+}
+
+/// A packed column of fixed-width values (`Int`, `Real`, `Decimal`,
+/// `DateTime`, `Duration`), sharing the null-bitmap/length/data layout that
+/// `ChunkedVecInt` originally hard-coded for `Int` alone.
+pub struct ChunkedVecSized<T: FixedWidth> {
     length: usize,
     null_cnt: usize,
     null_bitmap: Vec<u8>,
     data: Vec<u8>,
+    _phantom: PhantomData<T>,
 }
 
-impl ChunkedVecInt {
-    const ELEMENT_SIZE: usize = std::mem::size_of::<Int>();
-
+impl<T: FixedWidth> ChunkedVecSized<T> {
     pub fn new(init_cap: usize) -> Self {
-        ChunkedVecInt {
-            data: Vec::with_capacity(Self::ELEMENT_SIZE * init_cap),
+        ChunkedVecSized {
+            data: Vec::with_capacity(T::ELEMENT_SIZE * init_cap),
             null_bitmap: Vec::with_capacity((init_cap + 7) / 8),
             null_cnt: 0,
             length: 0,
+            _phantom: PhantomData,
         }
     }
 
-    pub fn from_vec(data: Vec<Option<Int>>) -> Self {
+    pub fn from_vec(data: Vec<Option<T>>) -> Self {
         let mut x = Self::new(data.len());
         for element in data {
-            if let Some(data) = element {
-                x.append(data).unwrap();
-            } else {
-                x.append_null();
-            }
+            x.append(element).unwrap();
         }
         x
     }
 
+    /// Update the null bitmap and count when append a datum.
+    /// `on` is false means the datum is null.
+    #[inline]
+    fn append_null_bitmap(&mut self, on: bool) {
+        let idx = self.length >> 3;
+        if idx >= self.null_bitmap.len() {
+            self.null_bitmap.push(0);
+        }
+        if on {
+            let pos = self.length & 7;
+            self.null_bitmap[idx] |= 1 << pos;
+        } else {
+            self.null_cnt += 1;
+        }
+    }
+
+    /// Append a non-null datum to the chunked vector.
+    #[inline]
+    pub fn append_value(&mut self, v: T) -> Result<()> {
+        v.encode_into(&mut self.data)?;
+        self.append_null_bitmap(true);
+        self.length += 1;
+        self.data.resize(self.length * T::ELEMENT_SIZE, 0);
+        Ok(())
+    }
+
+    /// Get a copy of the datum of the row in the chunked vector, decoded from
+    /// the packed buffer, or `None` if it is null.
+    pub fn get(&self, row_idx: usize) -> Option<T> {
+        if self.is_null(row_idx) {
+            None
+        } else {
+            let start = row_idx * T::ELEMENT_SIZE;
+            let end = start + T::ELEMENT_SIZE;
+            Some(T::decode_from(&self.data[start..end]))
+        }
+    }
+
+}
+
+impl<T: ArrowFixedWidth> ChunkedVecSized<T> {
+    /// Export this column as Arrow-compatible fixed-width array buffers:
+    /// the packed data buffer, and the validity bitmap padded to Arrow's
+    /// 64-byte boundary (still "1 = valid", same as `is_null`).
+    ///
+    /// Restricted to [`ArrowFixedWidth`] types, since only those have a
+    /// packed representation that actually matches Arrow's fixed-width
+    /// primitive layout.
+    pub fn into_arrow_buffers(&self) -> (Vec<u8>, Vec<u8>, usize, usize) {
+        (
+            self.data.clone(),
+            pad_validity_bitmap(&self.null_bitmap),
+            self.length,
+            self.null_cnt,
+        )
+    }
+
+    /// Rebuild a column from Arrow-compatible buffers produced by
+    /// `into_arrow_buffers` (or an external Arrow record batch using the
+    /// same fixed-width layout and validity convention). `validity` may be
+    /// empty, matching Arrow's convention of omitting the validity buffer
+    /// entirely when `null_count == 0`.
+    pub fn from_arrow_buffers(data: Vec<u8>, validity: Vec<u8>, len: usize, null_count: usize) -> Self {
+        let null_bitmap = if null_count == 0 || validity.is_empty() {
+            vec![0xff; (len + 7) / 8]
+        } else {
+            validity[..(len + 7) / 8].to_vec()
+        };
+        ChunkedVecSized {
+            data,
+            null_bitmap,
+            null_cnt: null_count,
+            length: len,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T: FixedWidth> ChunkedVec<T> for ChunkedVecSized<T> {
+    fn with_capacity(init_cap: usize) -> Self {
+        Self::new(init_cap)
+    }
+
+    fn append(&mut self, value: Option<T>) -> Result<()> {
+        match value {
+            Some(v) => self.append_value(v),
+            None => {
+                self.append_null();
+                Ok(())
+            }
+        }
+    }
+
+    #[inline]
+    fn append_null(&mut self) {
+        self.append_null_bitmap(false);
+        let len = T::ELEMENT_SIZE + self.data.len();
+        self.data.resize(len, 0);
+        self.length += 1;
+    }
+
     /// Return whether the datum for the row is null or not.
-    pub fn is_null(&self, row_idx: usize) -> bool {
+    fn is_null(&self, row_idx: usize) -> bool {
         if self.null_cnt == 0 {
             return false;
         }
@@ -50,10 +345,60 @@ impl ChunkedVecInt {
         }
     }
 
-    /// Update the null bitmap and count when append a datum.
-    /// `on` is false means the datum is null.
     #[inline]
-    pub fn append_null_bitmap(&mut self, on: bool) {
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn get_ref(&self, row_idx: usize) -> Option<T> {
+        self.get(row_idx)
+    }
+}
+
+/// Alias kept for compatibility with call sites that used the `Int`-only
+/// column before the chunked-vector family was generalized.
+pub type ChunkedVecInt = ChunkedVecSized<Int>;
+
+/// A column of variable-width values (`Bytes`, `Json`), stored as a
+/// contiguous data buffer plus a `Vec<usize>` of cumulative offsets, so that
+/// `get_slice` can hand back a zero-copy `&[u8]` slice into the buffer
+/// (`ChunkedVec::get_ref` returns an owned copy of that slice).
+pub struct ChunkedVecVar {
+    length: usize,
+    null_cnt: usize,
+    null_bitmap: Vec<u8>,
+    /// `var_offset[i]..var_offset[i + 1]` is the byte range of row `i`.
+    /// Has `length + 1` entries, starting at `0`.
+    var_offset: Vec<usize>,
+    data: Vec<u8>,
+}
+
+impl ChunkedVecVar {
+    pub fn new(init_cap: usize) -> Self {
+        let mut var_offset = Vec::with_capacity(init_cap + 1);
+        var_offset.push(0);
+        ChunkedVecVar {
+            data: Vec::with_capacity(init_cap * 4),
+            null_bitmap: Vec::with_capacity((init_cap + 7) / 8),
+            null_cnt: 0,
+            length: 0,
+            var_offset,
+        }
+    }
+
+    pub fn from_vec(data: Vec<Option<Vec<u8>>>) -> Self {
+        let mut x = Self::new(data.len());
+        for element in data {
+            match element {
+                Some(v) => x.append_value(&v),
+                None => x.append_null(),
+            }
+        }
+        x
+    }
+
+    #[inline]
+    fn append_null_bitmap(&mut self, on: bool) {
         let idx = self.length >> 3;
         if idx >= self.null_bitmap.len() {
             self.null_bitmap.push(0);
@@ -66,44 +411,47 @@ impl ChunkedVecInt {
         }
     }
 
-    /// Append null to the chunked vector.
+    /// Append a non-null datum to the chunked vector.
     #[inline]
-    pub fn append_null(&mut self) {
-        self.append_null_bitmap(false);
-        let len = Self::ELEMENT_SIZE + self.data.len();
-        self.data.resize(len, 0);
+    pub fn append_value(&mut self, v: &[u8]) {
+        self.data.extend_from_slice(v);
+        self.append_null_bitmap(true);
         self.length += 1;
+        self.var_offset.push(self.data.len());
     }
 
-    /// Called when datum has been appended.
-    #[inline]
-    fn finish_append(&mut self) {
-        self.append_null_bitmap(true);
-        self.length += 1;
-        self.data.resize(self.length * Self::ELEMENT_SIZE, 0);
+    /// Return whether the datum for the row is null or not.
+    pub fn is_null(&self, row_idx: usize) -> bool {
+        if self.null_cnt == 0 {
+            return false;
+        }
+
+        if let Some(null_byte) = self.null_bitmap.get(row_idx >> 3) {
+            null_byte & (1 << ((row_idx) & 7)) == 0
+        } else {
+            panic!("index out of range!");
+        }
     }
 
-    /// Append u64 datum to the chunked vector.
+    /// Append null to the chunked vector.
     #[inline]
-    pub fn append(&mut self, v: Int) -> Result<()> {
-        self.data.write_i64_le(v)?;
-        self.finish_append();
-        Ok(())
+    pub fn append_null(&mut self) {
+        self.append_null_bitmap(false);
+        self.length += 1;
+        self.var_offset.push(self.data.len());
     }
 
-    /// Get reference to datum of the row in the chunked vector.
-    pub fn get_ref(&self, row_idx: usize) -> Option<&Int> {
+    /// Get a zero-copy reference to the datum of the row in the chunked vector.
+    pub fn get_slice(&self, row_idx: usize) -> Option<&[u8]> {
         if self.is_null(row_idx) {
             None
         } else {
-            let start = row_idx * Self::ELEMENT_SIZE;
-            let end = start + Self::ELEMENT_SIZE;
-            let ref_data = &self.data[start..end];
-            Some(unsafe { std::mem::transmute::<&u8, &Int>(&ref_data[0]) })
+            let start = self.var_offset[row_idx];
+            let end = self.var_offset[row_idx + 1];
+            Some(&self.data[start..end])
         }
     }
 
-    /// Return the total rows in the column.
     #[inline]
     pub fn len(&self) -> usize {
         self.length
@@ -113,6 +461,75 @@ impl ChunkedVecInt {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Export this column as Arrow-compatible variable-width array buffers:
+    /// the offsets array, the contiguous data buffer, and the validity
+    /// bitmap padded to Arrow's 64-byte boundary. The buffers are copies of
+    /// this column's storage, not a zero-copy borrow.
+    pub fn into_arrow_buffers(&self) -> (Vec<usize>, Vec<u8>, Vec<u8>, usize, usize) {
+        (
+            self.var_offset.clone(),
+            self.data.clone(),
+            pad_validity_bitmap(&self.null_bitmap),
+            self.length,
+            self.null_cnt,
+        )
+    }
+
+    /// Rebuild a column from Arrow-compatible buffers produced by
+    /// `into_arrow_buffers`. `validity` may be empty, matching Arrow's
+    /// convention of omitting the validity buffer entirely when
+    /// `null_count == 0`.
+    pub fn from_arrow_buffers(
+        offsets: Vec<usize>,
+        data: Vec<u8>,
+        validity: Vec<u8>,
+        len: usize,
+        null_count: usize,
+    ) -> Self {
+        let null_bitmap = if null_count == 0 || validity.is_empty() {
+            vec![0xff; (len + 7) / 8]
+        } else {
+            validity[..(len + 7) / 8].to_vec()
+        };
+        ChunkedVecVar {
+            data,
+            null_bitmap,
+            null_cnt: null_count,
+            length: len,
+            var_offset: offsets,
+        }
+    }
+}
+
+impl ChunkedVec<Vec<u8>> for ChunkedVecVar {
+    fn with_capacity(init_cap: usize) -> Self {
+        Self::new(init_cap)
+    }
+
+    fn append(&mut self, value: Option<Vec<u8>>) -> Result<()> {
+        match value {
+            Some(v) => self.append_value(&v),
+            None => self.append_null(),
+        }
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        ChunkedVecVar::append_null(self)
+    }
+
+    fn is_null(&self, row_idx: usize) -> bool {
+        ChunkedVecVar::is_null(self, row_idx)
+    }
+
+    fn len(&self) -> usize {
+        ChunkedVecVar::len(self)
+    }
+
+    fn get_ref(&self, row_idx: usize) -> Option<Vec<u8>> {
+        self.get_slice(row_idx).map(|s| s.to_vec())
+    }
 }
 
 #[cfg(test)]
@@ -121,7 +538,7 @@ mod tests {
 
     #[test]
     fn test_chunked_int_element_len() {
-        assert_eq!(ChunkedVecInt::ELEMENT_SIZE, 8);
+        assert_eq!(<Int as FixedWidth>::ELEMENT_SIZE, 8);
     }
 
     #[test]
@@ -168,9 +585,99 @@ mod tests {
     fn test_chunked_read_ref() {
         let x = helper_new_chunked_int();
 
-        assert_eq!(*x.get_ref(1).unwrap(), 233);
-        assert_eq!(*x.get_ref(2).unwrap(), 65536);
-        assert_eq!(*x.get_ref(4).unwrap(), -233);
-        assert_eq!(*x.get_ref(5).unwrap(), 233333333);
+        assert_eq!(x.get(1).unwrap(), 233);
+        assert_eq!(x.get(2).unwrap(), 65536);
+        assert_eq!(x.get(4).unwrap(), -233);
+        assert_eq!(x.get(5).unwrap(), 233333333);
+    }
+
+    fn helper_new_chunked_var() -> ChunkedVecVar {
+        ChunkedVecVar::from_vec(vec![
+            None,
+            Some(b"hello".to_vec()),
+            Some(b"".to_vec()),
+            None,
+            Some(b"tikv".to_vec()),
+        ])
+    }
+
+    #[test]
+    fn test_chunked_var_null() {
+        let x = helper_new_chunked_var();
+        let result = vec![true, false, false, true, false];
+        for i in 0..x.len() {
+            assert_eq!(x.is_null(i), result[i]);
+        }
+    }
+
+    #[test]
+    fn test_chunked_var_read_slice() {
+        let x = helper_new_chunked_var();
+        assert_eq!(x.get_slice(1).unwrap(), b"hello");
+        assert_eq!(x.get_slice(2).unwrap(), b"");
+        assert_eq!(x.get_slice(4).unwrap(), b"tikv");
+    }
+
+    #[test]
+    fn test_chunked_int_arrow_roundtrip() {
+        let x = helper_new_chunked_int();
+        let (data, validity, len, null_count) = x.into_arrow_buffers();
+
+        assert_eq!(validity.len() % ARROW_BITMAP_ALIGNMENT, 0);
+        assert_eq!(len, 7);
+        assert_eq!(null_count, 3);
+
+        let y = ChunkedVecInt::from_arrow_buffers(data, validity, len, null_count);
+        assert_eq!(y.len(), x.len());
+        for i in 0..x.len() {
+            assert_eq!(y.is_null(i), x.is_null(i));
+            assert_eq!(y.get(i), x.get(i));
+        }
+    }
+
+    #[test]
+    fn test_chunked_var_arrow_roundtrip() {
+        let x = helper_new_chunked_var();
+        let (offsets, data, validity, len, null_count) = x.into_arrow_buffers();
+
+        assert_eq!(validity.len() % ARROW_BITMAP_ALIGNMENT, 0);
+        assert_eq!(len, 5);
+        assert_eq!(null_count, 2);
+
+        let y = ChunkedVecVar::from_arrow_buffers(offsets, data, validity, len, null_count);
+        assert_eq!(y.len(), x.len());
+        for i in 0..x.len() {
+            assert_eq!(y.is_null(i), x.is_null(i));
+            assert_eq!(y.get_slice(i), x.get_slice(i));
+        }
+    }
+
+    #[test]
+    fn test_chunked_int_from_arrow_buffers_omitted_validity() {
+        // Arrow omits the validity buffer entirely when `null_count == 0`.
+        let x = ChunkedVecInt::from_vec(vec![Some(1), Some(2), Some(3)]);
+        let (data, _validity, len, null_count) = x.into_arrow_buffers();
+        assert_eq!(null_count, 0);
+
+        let y = ChunkedVecInt::from_arrow_buffers(data, Vec::new(), len, null_count);
+        assert_eq!(y.len(), 3);
+        for i in 0..3 {
+            assert!(!y.is_null(i));
+        }
+        assert_eq!(y.get(0), Some(1));
+        assert_eq!(y.get(2), Some(3));
+    }
+
+    #[test]
+    fn test_chunked_var_from_arrow_buffers_omitted_validity() {
+        let x = ChunkedVecVar::from_vec(vec![Some(b"a".to_vec()), Some(b"b".to_vec())]);
+        let (offsets, data, _validity, len, null_count) = x.into_arrow_buffers();
+        assert_eq!(null_count, 0);
+
+        let y = ChunkedVecVar::from_arrow_buffers(offsets, data, Vec::new(), len, null_count);
+        assert_eq!(y.len(), 2);
+        assert!(!y.is_null(0));
+        assert!(!y.is_null(1));
+        assert_eq!(y.get_slice(0), Some(&b"a"[..]));
     }
 }
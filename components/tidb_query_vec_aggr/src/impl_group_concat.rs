@@ -0,0 +1,355 @@
+// Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::marker::PhantomData;
+
+use tidb_query_codegen::AggrFunction;
+use tidb_query_datatype::EvalType;
+use tipb::{Expr, ExprType, FieldType};
+
+use super::*;
+use tidb_query_common::Result;
+use tidb_query_datatype::codec::data_type::*;
+use tidb_query_datatype::expr::EvalContext;
+use tidb_query_vec_expr::{RpnExpression, RpnExpressionBuilder};
+
+const DEFAULT_GROUP_CONCAT_SEPARATOR: &[u8] = b",";
+
+/// The parser for GROUP_CONCAT aggregate function.
+pub struct AggrFnDefinitionParserGroupConcat;
+
+impl<'a> super::AggrDefinitionParser<'a> for AggrFnDefinitionParserGroupConcat {
+    fn check_supported(&self, aggr_def: &Expr) -> Result<()> {
+        assert_eq!(aggr_def.get_tp(), ExprType::GroupConcat);
+        // Unlike FIRST/LAST, GROUP_CONCAT is pushed down with the separator
+        // appended as an extra (constant) child after the value column, so
+        // at least two children are required instead of exactly one.
+        if aggr_def.get_children().len() < 2 {
+            return Err(other_err!(
+                "Expect at least 2 children for GROUP_CONCAT, got {}",
+                aggr_def.get_children().len()
+            ));
+        }
+        Ok(())
+    }
+
+    fn parse(
+        &'a self,
+        mut aggr_def: Expr,
+        ctx: &mut EvalContext,
+        src_schema: &[FieldType],
+        out_schema: &mut Vec<FieldType>,
+        out_exp: &mut Vec<RpnExpression>,
+    ) -> Result<Box<dyn super::AggrFunction<'a> + 'a>> {
+        use std::convert::TryFrom;
+        use tidb_query_datatype::FieldTypeAccessor;
+
+        assert_eq!(aggr_def.get_tp(), ExprType::GroupConcat);
+
+        // TiDB pushes the separator down as the last child expression (a
+        // constant), not as a field on `Expr` itself.
+        let mut children: Vec<Expr> = aggr_def.take_children().into_iter().collect();
+        if children.len() < 2 {
+            return Err(other_err!(
+                "Expect at least 2 children for GROUP_CONCAT, got {}",
+                children.len()
+            ));
+        }
+        let separator_expr = children.pop().unwrap();
+        let separator = match separator_expr.get_tp() {
+            ExprType::Bytes | ExprType::String => separator_expr.get_val().to_vec(),
+            _ => DEFAULT_GROUP_CONCAT_SEPARATOR.to_vec(),
+        };
+
+        let child = children.pop().unwrap();
+        let eval_type = EvalType::try_from(child.get_field_type().as_accessor().tp()).unwrap();
+
+        // GROUP_CONCAT always outputs a single `Bytes` column, regardless of
+        // the child's type.
+        out_schema.push(aggr_def.take_field_type());
+        out_exp.push(RpnExpressionBuilder::build_from_expr_tree(
+            child,
+            ctx,
+            src_schema.len(),
+        )?);
+
+        match_template::match_template! {
+            TT = [Int, Real, Duration, Decimal, DateTime],
+            match eval_type {
+                EvalType::TT => Ok(Box::new(AggrFnGroupConcat::<'_, &TT>::new(separator))),
+                EvalType::Json => Ok(Box::new(AggrFnGroupConcat::<'_, JsonRef>::new(separator))),
+                EvalType::Bytes => Ok(Box::new(AggrFnGroupConcat::<'_, BytesRef>::new(separator))),
+            }
+        }
+    }
+}
+
+/// The GROUP_CONCAT aggregate function.
+#[derive(Debug)]
+pub struct AggrFnGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    separator: Vec<u8>,
+    _phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T> AggrFnGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    fn new(separator: Vec<u8>) -> Self {
+        AggrFnGroupConcat {
+            separator,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> crate::AggrFunction<'a> for AggrFnGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    #[inline]
+    fn name(&self) -> &'static str {
+        "AggrFnGroupConcat"
+    }
+
+    #[inline]
+    fn create_state(&self) -> Box<dyn crate::AggrFunctionState<'a> + 'a> {
+        Box::new(AggrFnStateGroupConcat::<'a, T>::new(self.separator.clone()))
+    }
+}
+
+/// The state of the GROUP_CONCAT aggregate function: an accumulating byte
+/// buffer, plus whether any non-null value has been seen yet (so the
+/// leading separator can be suppressed).
+#[derive(Debug)]
+pub struct AggrFnStateGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    separator: Vec<u8>,
+    buffer: Vec<u8>,
+    has_value: bool,
+    _phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T> AggrFnStateGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    pub fn new(separator: Vec<u8>) -> Self {
+        AggrFnStateGroupConcat {
+            separator,
+            buffer: Vec::new(),
+            has_value: false,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> super::AggrFunctionStateUpdatePartial<'a, T> for AggrFnStateGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a + WriteAsText,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    #[inline]
+    unsafe fn update_unsafe(&mut self, _ctx: &mut EvalContext, value: Option<T>) -> Result<()> {
+        if let Some(value) = value {
+            if self.has_value {
+                self.buffer.extend_from_slice(&self.separator);
+            }
+            value.write_as_text(&mut self.buffer);
+            self.has_value = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn update_repeat_unsafe(
+        &mut self,
+        ctx: &mut EvalContext,
+        value: Option<T>,
+        repeat_times: usize,
+    ) -> Result<()> {
+        for _ in 0..repeat_times {
+            self.update_unsafe(ctx, value)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn update_vector_unsafe(
+        &mut self,
+        ctx: &mut EvalContext,
+        _phantom_data: Option<T>,
+        physical_values: T::ChunkedType,
+        logical_rows: &[usize],
+    ) -> Result<()> {
+        for physical_index in logical_rows {
+            self.update_unsafe(ctx, physical_values.get_option_ref(*physical_index))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, 'b, T1, T2> super::AggrFunctionStateUpdatePartial<'a, T1> for AggrFnStateGroupConcat<'b, T2>
+where
+    T1: EvaluableRef<'a> + 'a,
+    T2: EvaluableRef<'b> + 'b,
+    VectorValue: VectorValueExt<T2::EvaluableType>,
+{
+    #[inline]
+    default unsafe fn update_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _value: Option<T1>,
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+
+    #[inline]
+    default unsafe fn update_repeat_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _value: Option<T1>,
+        _repeat_times: usize,
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+
+    #[inline]
+    default unsafe fn update_vector_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _phantom_data: Option<T1>,
+        _physical_values: T1::ChunkedType,
+        _logical_rows: &[usize],
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+}
+
+impl<'a, T> super::AggrFunctionState<'a> for AggrFnStateGroupConcat<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    fn push_result(&self, _ctx: &mut EvalContext, target: &mut [VectorValue]) -> Result<()> {
+        assert_eq!(target.len(), 1);
+        let res = if self.has_value {
+            Some(self.buffer.clone())
+        } else {
+            None
+        };
+        target[0].push(res);
+        Ok(())
+    }
+}
+
+/// Render a value the way MySQL's `GROUP_CONCAT` stringifies its argument.
+/// `Bytes`/`BytesRef` are appended verbatim; everything else uses its usual
+/// textual representation.
+trait WriteAsText {
+    fn write_as_text(&self, buffer: &mut Vec<u8>);
+}
+
+impl WriteAsText for &Int {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+impl WriteAsText for &Real {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+impl WriteAsText for &Decimal {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+impl WriteAsText for &DateTime {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+impl WriteAsText for &Duration {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+impl<'a> WriteAsText for BytesRef<'a> {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self);
+    }
+}
+
+impl<'a> WriteAsText for JsonRef<'a> {
+    fn write_as_text(&self, buffer: &mut Vec<u8>) {
+        buffer.extend_from_slice(self.to_string().as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AggrFunction;
+    use super::*;
+
+    use tidb_query_datatype::FieldTypeTp;
+    use tipb_helper::ExprDefBuilder;
+
+    use crate::AggrDefinitionParser;
+
+    fn new_int_function() -> Box<dyn AggrFunction<'static>> {
+        Box::new(AggrFnGroupConcat::<&'static Int>::new(b",".to_vec()))
+    }
+
+    #[test]
+    fn test_update_skips_null_and_joins_with_separator() {
+        let mut ctx = EvalContext::default();
+        let function = new_int_function();
+        let mut state = function.create_state();
+        let mut result = [VectorValue::with_capacity(0, EvalType::Bytes)];
+
+        update!(state, &mut ctx, None::<&Int>).unwrap();
+        update!(state, &mut ctx, Some(&1)).unwrap();
+        update!(state, &mut ctx, None::<&Int>).unwrap();
+        update!(state, &mut ctx, Some(&2)).unwrap();
+
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_bytes_slice(), &[Some(b"1,2".to_vec())]);
+    }
+
+    #[test]
+    fn test_empty_group_pushes_null() {
+        let mut ctx = EvalContext::default();
+        let function = new_int_function();
+        let mut state = function.create_state();
+        let mut result = [VectorValue::with_capacity(0, EvalType::Bytes)];
+
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_bytes_slice(), &[None]);
+    }
+
+    #[test]
+    fn test_illegal_request() {
+        // Missing the separator child that TiDB normally appends.
+        let expr = ExprDefBuilder::aggr_func(ExprType::GroupConcat, FieldTypeTp::VarString)
+            .push_child(ExprDefBuilder::column_ref(0, FieldTypeTp::LongLong))
+            .build();
+        AggrFnDefinitionParserGroupConcat
+            .check_supported(&expr)
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
+
+use std::marker::PhantomData;
+
+use tidb_query_codegen::AggrFunction;
+use tidb_query_datatype::EvalType;
+use tipb::{Expr, ExprType, FieldType};
+
+use super::*;
+use tidb_query_common::Result;
+use tidb_query_datatype::codec::data_type::*;
+use tidb_query_datatype::expr::EvalContext;
+use tidb_query_vec_expr::{RpnExpression, RpnExpressionBuilder};
+
+/// The parser for LAST aggregate function.
+pub struct AggrFnDefinitionParserLast;
+
+impl <'a> super::AggrDefinitionParser<'a> for AggrFnDefinitionParserLast {
+    fn check_supported(&self, aggr_def: &Expr) -> Result<()> {
+        assert_eq!(aggr_def.get_tp(), ExprType::Last);
+        super::util::check_aggr_exp_supported_one_child(aggr_def)
+    }
+
+    fn parse(
+        &'a self,
+        mut aggr_def: Expr,
+        ctx: &mut EvalContext,
+        src_schema: &[FieldType],
+        out_schema: &mut Vec<FieldType>,
+        out_exp: &mut Vec<RpnExpression>,
+    ) -> Result<Box<dyn super::AggrFunction<'a> + 'a>> {
+        use std::convert::TryFrom;
+        use tidb_query_datatype::FieldTypeAccessor;
+
+        assert_eq!(aggr_def.get_tp(), ExprType::Last);
+        let child = aggr_def.take_children().into_iter().next().unwrap();
+        let eval_type = EvalType::try_from(child.get_field_type().as_accessor().tp()).unwrap();
+
+        let out_ft = aggr_def.take_field_type();
+        let out_et = box_try!(EvalType::try_from(out_ft.as_accessor().tp()));
+
+        if out_et != eval_type {
+            return Err(other_err!(
+                "Unexpected return field type {}",
+                out_ft.as_accessor().tp()
+            ));
+        }
+
+        // LAST outputs one column with the same type as its child
+        out_schema.push(out_ft);
+        out_exp.push(RpnExpressionBuilder::build_from_expr_tree(
+            child,
+            ctx,
+            src_schema.len(),
+        )?);
+
+        match_template::match_template! {
+            TT = [Int, Real, Duration, Decimal, DateTime],
+            match eval_type {
+                EvalType::TT => Ok(Box::new(AggrFnLast::<'_, &TT>::new())),
+                EvalType::Json => Ok(Box::new(AggrFnLast::<'_, JsonRef>::new())),
+                EvalType::Bytes => Ok(Box::new(AggrFnLast::<'_, BytesRef>::new())),
+            }
+        }
+    }
+}
+
+/// The LAST aggregate function.
+#[derive(Debug)]
+pub struct AggrFnLast<'a, T>(PhantomData<&'a T>)
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>;
+
+impl<'a, T> crate::AggrFunction<'a> for AggrFnLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    #[inline]
+    fn name(&self) -> &'static str {
+        "AggrFnLast"
+    }
+    #[inline]
+    fn create_state(&self) -> Box<dyn crate::AggrFunctionState<'a> + 'a> {
+        Box::new(AggrFnStateLast::<'a, T>::new())
+    }
+}
+
+impl<'a, T> AggrFnLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    fn new() -> Self {
+        AggrFnLast(PhantomData)
+    }
+}
+
+/// The state of the LAST aggregate function.
+#[derive(Debug)]
+pub enum AggrFnStateLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    Empty,
+    Valued(Option<T::EvaluableType>),
+}
+
+impl<'a, T> AggrFnStateLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    pub fn new() -> Self {
+        AggrFnStateLast::Empty
+    }
+}
+
+// Here we manually implement `AggrFunctionStateUpdatePartial` instead of implementing
+// `ConcreteAggrFunctionState` so that `update_repeat` and `update_vector` can be faster.
+impl<'a, T> super::AggrFunctionStateUpdatePartial<'a, T> for AggrFnStateLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    // ChunkedType has been implemented in AggrFunctionStateUpdatePartial<T1> for AggrFnStateLast<T2>
+
+    #[inline]
+    unsafe fn update_unsafe(&mut self, _ctx: &mut EvalContext, value: Option<T>) -> Result<()> {
+        // Unlike FIRST, LAST overwrites the stored value on every update.
+        // TODO: avoid this clone
+        *self = AggrFnStateLast::Valued(value.map(|x| x.to_owned_value()));
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn update_repeat_unsafe(
+        &mut self,
+        ctx: &mut EvalContext,
+        value: Option<T>,
+        repeat_times: usize,
+    ) -> Result<()> {
+        assert!(repeat_times > 0);
+        self.update_unsafe(ctx, value)
+    }
+
+    #[inline]
+    unsafe fn update_vector_unsafe(
+        &mut self,
+        ctx: &mut EvalContext,
+        _phantom_data: Option<T>,
+        physical_values: T::ChunkedType,
+        logical_rows: &[usize],
+    ) -> Result<()> {
+        if let Some(physical_index) = logical_rows.last() {
+            self.update_unsafe(ctx, physical_values.get_option_ref(*physical_index))?;
+        }
+        Ok(())
+    }
+}
+
+// In order to make `AggrFnStateLast` satisfy the `AggrFunctionState` trait, we default impl all
+// `AggrFunctionStateUpdatePartial` of `Evaluable` for all `AggrFnStateLast`.
+impl<'a, 'b, T1, T2> super::AggrFunctionStateUpdatePartial<'a, T1> for AggrFnStateLast<'b, T2>
+where
+    T1: EvaluableRef<'a> + 'a,
+    T2: EvaluableRef<'b> + 'b,
+    VectorValue: VectorValueExt<T2::EvaluableType>,
+{
+    #[inline]
+    default unsafe fn update_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _value: Option<T1>,
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+
+    #[inline]
+    default unsafe fn update_repeat_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _value: Option<T1>,
+        _repeat_times: usize,
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+
+    #[inline]
+    default unsafe fn update_vector_unsafe(
+        &mut self,
+        _ctx: &mut EvalContext,
+        _phantom_data: Option<T1>,
+        _physical_values: T1::ChunkedType,
+        _logical_rows: &[usize],
+    ) -> Result<()> {
+        panic!("Unmatched parameter type")
+    }
+}
+
+impl<'a, T> super::AggrFunctionState<'a> for AggrFnStateLast<'a, T>
+where
+    T: EvaluableRef<'a> + 'a,
+    VectorValue: VectorValueExt<T::EvaluableType>,
+{
+    fn push_result(&self, _ctx: &mut EvalContext, target: &mut [VectorValue]) -> Result<()> {
+        assert_eq!(target.len(), 1);
+        let res = if let AggrFnStateLast::Valued(v) = self {
+            v.clone()
+        } else {
+            None
+        };
+        target[0].push(res);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::AggrFunction;
+    use super::*;
+
+    use tidb_query_datatype::FieldTypeTp;
+    use tipb_helper::ExprDefBuilder;
+
+    use crate::AggrDefinitionParser;
+
+    #[test]
+    fn test_update() {
+        let mut ctx = EvalContext::default();
+        let function = AggrFnLast::<&'static Int>::new();
+        let mut state = function.create_state();
+
+        let mut result = [VectorValue::with_capacity(0, EvalType::Int)];
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[None]);
+
+        update!(state, &mut ctx, Some(&1)).unwrap();
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[None, Some(1)]);
+
+        update!(state, &mut ctx, Some(&2)).unwrap();
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[None, Some(1), Some(2)]);
+
+        update!(state, &mut ctx, None::<&Int>).unwrap();
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[None, Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn test_update_vector() {
+        let mut ctx = EvalContext::default();
+        let function = AggrFnLast::<&'static Int>::new();
+        let mut state = function.create_state();
+        let mut result = [VectorValue::with_capacity(0, EvalType::Int)];
+
+        update_vector!(
+            state,
+            &mut ctx,
+            &NotChunkedVec::from_slice(&[Some(1), Some(2)]),
+            &[0, 1]
+        )
+        .unwrap();
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[Some(2)]);
+
+        // Reset state
+        let mut state = function.create_state();
+
+        result[0].clear();
+        update_vector!(
+            state,
+            &mut ctx,
+            &NotChunkedVec::from_slice(&[None, Some(2)]),
+            &[1, 0]
+        )
+        .unwrap();
+        state.push_result(&mut ctx, &mut result[..]).unwrap();
+        assert_eq!(result[0].as_int_slice(), &[None]);
+    }
+
+    #[test]
+    fn test_illegal_request() {
+        let expr = ExprDefBuilder::aggr_func(ExprType::Last, FieldTypeTp::Double) // Expect LongLong but give Double
+            .push_child(ExprDefBuilder::column_ref(0, FieldTypeTp::LongLong))
+            .build();
+        AggrFnDefinitionParserLast.check_supported(&expr).unwrap();
+
+        let src_schema = [FieldTypeTp::LongLong.into()];
+        let mut schema = vec![];
+        let mut exp = vec![];
+        let mut ctx = EvalContext::default();
+        AggrFnDefinitionParserLast
+            .parse(expr, &mut ctx, &src_schema, &mut schema, &mut exp)
+            .unwrap_err();
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2019 TiKV Project Authors. Licensed under Apache-2.0.
+
+use openssl::hash::{self, MessageDigest};
+use tidb_query_codegen::rpn_fn;
+
+use tidb_query_common::Result;
+use tidb_query_datatype::codec::data_type::{Bytes, Int};
+use tidb_query_datatype::codec::Error;
+
+const BASE64_LINE_WRAP_LENGTH: usize = 76;
+
+#[rpn_fn]
+#[inline]
+pub fn uncompressed_length(arg: &Bytes) -> Result<Option<Int>> {
+    Ok(Some(if arg.len() <= 4 {
+        0
+    } else {
+        let len = u32::from_le_bytes([arg[0], arg[1], arg[2], arg[3]]);
+        i64::from(len)
+    }))
+}
+
+#[rpn_fn]
+#[inline]
+pub fn md5(arg: &Bytes) -> Result<Option<Bytes>> {
+    hex_digest(MessageDigest::md5(), arg)
+}
+
+#[rpn_fn]
+#[inline]
+pub fn sha1(arg: &Bytes) -> Result<Option<Bytes>> {
+    hex_digest(MessageDigest::sha1(), arg)
+}
+
+#[rpn_fn]
+#[inline]
+pub fn sha2(arg: &Bytes, hash_length: &Int) -> Result<Option<Bytes>> {
+    let digest = match hash_length {
+        224 => MessageDigest::sha224(),
+        0 | 256 => MessageDigest::sha256(),
+        384 => MessageDigest::sha384(),
+        512 => MessageDigest::sha512(),
+        _ => return Ok(None),
+    };
+    hex_digest(digest, arg)
+}
+
+fn hex_digest(hashtype: MessageDigest, input: &[u8]) -> Result<Option<Bytes>> {
+    hash::hash(hashtype, input)
+        .map(|digest| Some(hex::encode(digest).into_bytes()))
+        .map_err(|e| box_err!("OpenSSL error: {:?}", e))
+}
+
+#[rpn_fn]
+#[inline]
+pub fn random_bytes(arg: &Int) -> Result<Option<Bytes>> {
+    if *arg < 1 || *arg > 1024 {
+        return Err(other_err!("Incorrect arguments to random_bytes"));
+    }
+    let mut out = vec![0u8; *arg as usize];
+    openssl::rand::rand_bytes(&mut out).map_err(|e| box_err!("OpenSSL error: {:?}", e))?;
+    Ok(Some(out))
+}
+
+/// `TO_BASE64(str)`: encode `str` with the standard base64 alphabet, wrapping
+/// the output every 76 characters with `\n`, matching MySQL's behavior.
+#[rpn_fn]
+#[inline]
+pub fn to_base64(arg: &Bytes) -> Result<Option<Bytes>> {
+    if arg.is_empty() {
+        return Ok(Some(Vec::new()));
+    }
+
+    let unwrapped = base64::encode(arg);
+    let encoded_len = unwrapped.len();
+    let line_count = (encoded_len + BASE64_LINE_WRAP_LENGTH - 1) / BASE64_LINE_WRAP_LENGTH;
+    let mut result = Vec::with_capacity(encoded_len + line_count);
+    for (i, chunk) in unwrapped.as_bytes().chunks(BASE64_LINE_WRAP_LENGTH).enumerate() {
+        if i > 0 {
+            result.push(b'\n');
+        }
+        result.extend_from_slice(chunk);
+    }
+    Ok(Some(result))
+}
+
+/// `FROM_BASE64(str)`: decode `str`, ignoring embedded newlines (as produced
+/// by `TO_BASE64`); returns `NULL` for malformed input rather than an error
+/// so it composes inside larger pushed-down expressions.
+#[rpn_fn]
+#[inline]
+pub fn from_base64(arg: &Bytes) -> Result<Option<Bytes>> {
+    let input: Vec<u8> = arg.iter().copied().filter(|&b| b != b'\n' && b != b'\r').collect();
+    Ok(base64::decode(&input).ok())
+}
+
+/// `HEX(str)`: uppercase hex encoding of `str`'s raw bytes.
+#[rpn_fn]
+#[inline]
+pub fn hex_str_arg(arg: &Bytes) -> Result<Option<Bytes>> {
+    Ok(Some(hex::encode_upper(arg).into_bytes()))
+}
+
+/// `HEX(n)`: format the integer argument as an uppercase hex string,
+/// matching MySQL's `HEX` overload for numeric arguments.
+#[rpn_fn]
+#[inline]
+pub fn hex_int_arg(arg: &Int) -> Result<Option<Bytes>> {
+    Ok(Some(format!("{:X}", arg).into_bytes()))
+}
+
+/// `UNHEX(str)`: decode a hex string back to raw bytes; returns `NULL` for
+/// odd-length or non-hex input.
+#[rpn_fn]
+#[inline]
+pub fn unhex(arg: &Bytes) -> Result<Option<Bytes>> {
+    if arg.len() % 2 != 0 {
+        return Ok(None);
+    }
+    Ok(hex::decode(arg).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_base64_wraps_at_76_chars() {
+        let input = vec![b'a'; 100];
+        let output = to_base64(&input).unwrap().unwrap();
+        let as_str = String::from_utf8(output).unwrap();
+        let first_line_len = as_str.split('\n').next().unwrap().len();
+        assert_eq!(first_line_len, BASE64_LINE_WRAP_LENGTH);
+    }
+
+    #[test]
+    fn test_from_base64_roundtrip() {
+        let input = b"tikv".to_vec();
+        let encoded = to_base64(&input).unwrap().unwrap();
+        let decoded = from_base64(&encoded).unwrap().unwrap();
+        assert_eq!(decoded, input);
+    }
+
+    #[test]
+    fn test_from_base64_invalid_returns_null() {
+        let result = from_base64(&b"not valid base64!!".to_vec()).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_hex_str() {
+        assert_eq!(
+            hex_str_arg(&b"abc".to_vec()).unwrap().unwrap(),
+            b"616263".to_vec()
+        );
+    }
+
+    #[test]
+    fn test_hex_int() {
+        assert_eq!(hex_int_arg(&255).unwrap().unwrap(), b"FF".to_vec());
+    }
+
+    #[test]
+    fn test_unhex_roundtrip() {
+        let original = b"abc".to_vec();
+        let hexed = hex_str_arg(&original).unwrap().unwrap();
+        assert_eq!(unhex(&hexed).unwrap().unwrap(), original);
+    }
+
+    #[test]
+    fn test_unhex_odd_length_returns_null() {
+        assert!(unhex(&b"abc".to_vec()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unhex_non_hex_returns_null() {
+        assert!(unhex(&b"zz".to_vec()).unwrap().is_none());
+    }
+}
@@ -0,0 +1,302 @@
+// Copyright 2020 TiKV Project Authors. Licensed under Apache-2.0.
+
+use tidb_query_codegen::rpn_fn;
+
+use tidb_query_common::Result;
+use tidb_query_datatype::codec::data_type::{Bytes, DateTime};
+use tidb_query_datatype::codec::mysql::{Time, TimeArgs, TimeType};
+use tidb_query_datatype::expr::EvalContext;
+
+/// `STR_TO_DATE(str, format)`: parse `str` against a MySQL-style `format`
+/// string and return the resulting `DateTime`, or `NULL` if `str` does not
+/// match `format`.
+///
+/// Unlike most scalar functions, a malformed match is not an error: TiDB
+/// returns `NULL` so the row is simply excluded rather than aborting the
+/// whole query.
+#[rpn_fn(capture = [ctx])]
+#[inline]
+pub fn str_to_date(ctx: &mut EvalContext, date: &Bytes, format: &Bytes) -> Result<Option<DateTime>> {
+    let date = match std::str::from_utf8(date) {
+        Ok(date) => date,
+        Err(_) => return Ok(None),
+    };
+    let format = match std::str::from_utf8(format) {
+        Ok(format) => format,
+        Err(_) => return Ok(None),
+    };
+
+    match parse_with_format(date, format) {
+        Some(broken_down) => Ok(broken_down.into_date_time(ctx).ok()),
+        None => Ok(None),
+    }
+}
+
+/// `DATE_FORMAT(date, format)`: render `date` according to a MySQL-style
+/// `format` string.
+#[rpn_fn(capture = [ctx])]
+#[inline]
+pub fn date_format(ctx: &mut EvalContext, date: &DateTime, format: &Bytes) -> Result<Option<Bytes>> {
+    let format = match std::str::from_utf8(format) {
+        Ok(format) => format,
+        Err(_) => return Ok(None),
+    };
+    Ok(Some(format_date_time(ctx, date, format).into_bytes()))
+}
+
+/// A partially (or fully) parsed date/time, built up field-by-field while
+/// scanning a format string against an input string in `parse_with_format`.
+#[derive(Default, Clone, Copy)]
+struct BrokenDownTime {
+    year: u32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    micros: u32,
+    is_pm: Option<bool>,
+}
+
+impl BrokenDownTime {
+    fn into_date_time(mut self, ctx: &mut EvalContext) -> std::result::Result<DateTime, ()> {
+        if let Some(is_pm) = self.is_pm {
+            // %h/%I is a 12-hour hour; fold the %p marker into a 24-hour value.
+            self.hour %= 12;
+            if is_pm {
+                self.hour += 12;
+            }
+        }
+        if self.month == 0 || self.month > 12 || self.day == 0 || self.day > 31 {
+            return Err(());
+        }
+        if self.hour > 23 || self.minute > 59 || self.second > 59 {
+            return Err(());
+        }
+        Time::new(
+            ctx,
+            TimeArgs {
+                year: self.year,
+                month: self.month,
+                day: self.day,
+                hour: self.hour,
+                minute: self.minute,
+                second: self.second,
+                micro: self.micros,
+                fsp: 6,
+                time_type: TimeType::DateTime,
+            },
+        )
+        .map_err(|_| ())
+    }
+}
+
+/// Scan `format` for `%`-escapes, consuming `input` positionally for each
+/// field and matching literal characters verbatim. Returns `None` as soon
+/// as the input stops matching the pattern, mirroring TiDB's permissive
+/// "return NULL, don't error" behavior for `STR_TO_DATE`. The entire input
+/// must be consumed by the pattern; trailing garbage (e.g.
+/// `STR_TO_DATE('2020-01-01xyz', '%Y-%m-%d')`) is rejected rather than
+/// silently ignored.
+fn parse_with_format(input: &str, format: &str) -> Option<BrokenDownTime> {
+    let mut result = BrokenDownTime::default();
+    let mut chars = input.chars().peekable();
+    let mut fmt_chars = format.chars().peekable();
+
+    while let Some(fc) = fmt_chars.next() {
+        if fc != '%' {
+            if chars.next() != Some(fc) {
+                return None;
+            }
+            continue;
+        }
+
+        let spec = fmt_chars.next()?;
+        match spec {
+            'Y' => result.year = take_digits(&mut chars, 4)?,
+            'y' => {
+                let y = take_digits(&mut chars, 2)?;
+                result.year = if y < 70 { 2000 + y } else { 1900 + y };
+            }
+            'm' | 'c' => result.month = take_digits(&mut chars, 2)?,
+            'd' | 'e' => result.day = take_digits(&mut chars, 2)?,
+            'H' | 'k' => result.hour = take_digits(&mut chars, 2)?,
+            'h' | 'I' => result.hour = take_digits(&mut chars, 2)?,
+            'i' => result.minute = take_digits(&mut chars, 2)?,
+            's' => result.second = take_digits(&mut chars, 2)?,
+            'f' => {
+                // Microseconds are left-aligned in the input (e.g. "5" means
+                // 500000us), so pad on the right up to 6 digits.
+                let digits = take_raw_digits(&mut chars, 6)?;
+                let mut padded = digits;
+                while padded.len() < 6 {
+                    padded.push('0');
+                }
+                result.micros = padded.parse().ok()?;
+            }
+            'p' => {
+                let marker: String = (0..2).filter_map(|_| chars.next()).collect();
+                result.is_pm = match marker.to_ascii_uppercase().as_str() {
+                    "AM" => Some(false),
+                    "PM" => Some(true),
+                    _ => return None,
+                };
+            }
+            '%' => {
+                if chars.next() != Some('%') {
+                    return None;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    Some(result)
+}
+
+/// Consume up to `max` ASCII digits from `chars` and parse them as a number.
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars>, max: usize) -> Option<u32> {
+    let digits = take_raw_digits(chars, max)?;
+    if digits.is_empty() {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+/// Consume up to `max` ASCII digits from `chars` without parsing them.
+fn take_raw_digits(chars: &mut std::iter::Peekable<std::str::Chars>, max: usize) -> Option<String> {
+    let mut digits = String::new();
+    while digits.len() < max {
+        match chars.peek() {
+            Some(c) if c.is_ascii_digit() => digits.push(chars.next().unwrap()),
+            _ => break,
+        }
+    }
+    Some(digits)
+}
+
+/// Walk `format` and substitute each `%`-escape from `date`'s fields.
+fn format_date_time(ctx: &mut EvalContext, date: &DateTime, format: &str) -> String {
+    let mut result = String::with_capacity(format.len());
+    let mut fmt_chars = format.chars();
+
+    while let Some(fc) = fmt_chars.next() {
+        if fc != '%' {
+            result.push(fc);
+            continue;
+        }
+
+        match fmt_chars.next() {
+            Some('Y') => result.push_str(&format!("{:04}", date.year())),
+            Some('y') => result.push_str(&format!("{:02}", date.year() % 100)),
+            Some('m') => result.push_str(&format!("{:02}", date.month())),
+            Some('c') => result.push_str(&date.month().to_string()),
+            Some('d') => result.push_str(&format!("{:02}", date.day())),
+            Some('e') => result.push_str(&date.day().to_string()),
+            Some('H') => result.push_str(&format!("{:02}", date.hour())),
+            Some('k') => result.push_str(&date.hour().to_string()),
+            Some('h') | Some('I') => {
+                let h12 = match date.hour() % 12 {
+                    0 => 12,
+                    h => h,
+                };
+                result.push_str(&format!("{:02}", h12));
+            }
+            Some('i') => result.push_str(&format!("{:02}", date.minute())),
+            Some('s') => result.push_str(&format!("{:02}", date.second())),
+            Some('f') => result.push_str(&format!("{:06}", date.micro())),
+            Some('p') => result.push_str(if date.hour() >= 12 { "PM" } else { "AM" }),
+            Some('%') => result.push('%'),
+            Some(other) => {
+                result.push('%');
+                result.push(other);
+            }
+            None => result.push('%'),
+        }
+    }
+
+    let _ = ctx;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tidb_query_datatype::expr::EvalContext;
+
+    #[test]
+    fn test_str_to_date_two_digit_year_pivot() {
+        let mut ctx = EvalContext::default();
+        let d = str_to_date(
+            &mut ctx,
+            &b"69-01-01".to_vec(),
+            &b"%y-%m-%d".to_vec(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(d.year(), 2069);
+
+        let d = str_to_date(
+            &mut ctx,
+            &b"70-01-01".to_vec(),
+            &b"%y-%m-%d".to_vec(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(d.year(), 1970);
+    }
+
+    #[test]
+    fn test_str_to_date_microseconds_padding() {
+        let mut ctx = EvalContext::default();
+        let d = str_to_date(
+            &mut ctx,
+            &b"2020-01-01 00:00:00.5".to_vec(),
+            &b"%Y-%m-%d %H:%i:%s.%f".to_vec(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(d.micro(), 500_000);
+    }
+
+    #[test]
+    fn test_str_to_date_am_pm_roundtrip() {
+        let mut ctx = EvalContext::default();
+        let d = str_to_date(
+            &mut ctx,
+            &b"2020-01-01 01:02:03 PM".to_vec(),
+            &b"%Y-%m-%d %h:%i:%s %p".to_vec(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(d.hour(), 13);
+
+        let formatted = date_format(&mut ctx, &d, &b"%Y-%m-%d %h:%i:%s %p".to_vec())
+            .unwrap()
+            .unwrap();
+        assert_eq!(formatted, b"2020-01-01 01:02:03 PM".to_vec());
+    }
+
+    #[test]
+    fn test_str_to_date_malformed_returns_null() {
+        let mut ctx = EvalContext::default();
+        let d = str_to_date(&mut ctx, &b"not-a-date".to_vec(), &b"%Y-%m-%d".to_vec()).unwrap();
+        assert!(d.is_none());
+    }
+
+    #[test]
+    fn test_str_to_date_trailing_garbage_returns_null() {
+        let mut ctx = EvalContext::default();
+        let d = str_to_date(
+            &mut ctx,
+            &b"2020-01-01xyz".to_vec(),
+            &b"%Y-%m-%d".to_vec(),
+        )
+        .unwrap();
+        assert!(d.is_none());
+    }
+}
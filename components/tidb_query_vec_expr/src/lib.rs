@@ -30,6 +30,7 @@ pub mod types;
 pub mod impl_control;
 pub mod impl_encryption;
 pub mod impl_json;
+pub mod impl_time;
 
 pub use self::types::*;
 
@@ -43,6 +44,7 @@ use tidb_query_datatype::codec::data_type::*;
 use self::impl_control::*;
 use self::impl_encryption::*;
 use self::impl_json::*;
+use self::impl_time::*;
 
 #[rustfmt::skip]
 fn map_expr_node_to_rpn_func(expr: &Expr) -> Result<RpnFnMeta> {
@@ -78,6 +80,11 @@ fn map_expr_node_to_rpn_func(expr: &Expr) -> Result<RpnFnMeta> {
         ScalarFuncSig::Sha1 => sha1_fn_meta(),
         ScalarFuncSig::Sha2 => sha2_fn_meta(),
         ScalarFuncSig::RandomBytes => random_bytes_fn_meta(),
+        ScalarFuncSig::ToBase64 => to_base64_fn_meta(),
+        ScalarFuncSig::FromBase64 => from_base64_fn_meta(),
+        ScalarFuncSig::HexStrArg => hex_str_arg_fn_meta(),
+        ScalarFuncSig::HexIntArg => hex_int_arg_fn_meta(),
+        ScalarFuncSig::UnHex => unhex_fn_meta(),
         // impl_json
         ScalarFuncSig::JsonDepthSig => json_depth_fn_meta(),
         ScalarFuncSig::JsonTypeSig => json_type_fn_meta(),
@@ -93,6 +100,9 @@ fn map_expr_node_to_rpn_func(expr: &Expr) -> Result<RpnFnMeta> {
         ScalarFuncSig::JsonRemoveSig => json_remove_fn_meta(),
         ScalarFuncSig::JsonKeysSig => json_keys_fn_meta(),
         ScalarFuncSig::JsonKeys2ArgsSig => json_keys_fn_meta(),
+        // impl_time
+        ScalarFuncSig::StrToDate => str_to_date_fn_meta(),
+        ScalarFuncSig::DateFormatSig => date_format_fn_meta(),
         _ => return Err(other_err!(
             "ScalarFunction {:?} is not supported in batch mode",
             value